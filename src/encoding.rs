@@ -1,5 +1,5 @@
 /// Message decoding error
-#[derive(thiserror::Error, Debug, Clone)]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
 pub enum MessageDecodeError {
     /// More data was expected but none is available
     #[error("eof unexpected")]
@@ -7,9 +7,30 @@ pub enum MessageDecodeError {
     /// A received string could not be decoded as UTF8
     #[error("invalid utf8 string")]
     Utf8Error(#[from] std::str::Utf8Error),
+    /// The underlying `std::io::Read`/`std::io::Write` failed
+    #[error("io error ({kind:?}): {message}")]
+    Io {
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    /// A TLV record used an even (required) type this reader doesn't recognize
+    #[error("unknown required tlv field: {0}")]
+    UnknownRequiredTlvField(u64),
+    /// A TLV record's type did not strictly increase over the previous one
+    #[error("tlv records out of order: type {next} did not follow type {prev}")]
+    TlvRecordsOutOfOrder { prev: u64, next: u64 },
 }
 
-fn encode_vlq_int(output: &mut Vec<u8>, v: u32) {
+impl From<std::io::Error> for MessageDecodeError {
+    fn from(err: std::io::Error) -> Self {
+        MessageDecodeError::Io {
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+}
+
+pub(crate) fn encode_vlq_int(output: &mut Vec<u8>, v: u32) {
     let sv = v as i32;
     if !(-(1 << 26)..(3 << 26)).contains(&sv) {
         output.push(((sv >> 28) & 0x7F) as u8 | 0x80);
@@ -36,7 +57,23 @@ pub(crate) fn next_byte(data: &mut &[u8]) -> Result<u8, MessageDecodeError> {
     }
 }
 
-fn parse_vlq_int(data: &mut &[u8]) -> Result<u32, MessageDecodeError> {
+pub(crate) fn next_byte_reader<R: std::io::Read>(
+    reader: &mut R,
+) -> Result<u8, MessageDecodeError> {
+    let mut buf = [0u8; 1];
+    // `read_exact` retries on `ErrorKind::Interrupted` internally, so a
+    // transient signal interruption on a serial/MCU link doesn't surface as a
+    // decode failure; a short read past EOF comes back as `UnexpectedEof`.
+    match reader.read_exact(&mut buf) {
+        Ok(()) => Ok(buf[0]),
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+            Err(MessageDecodeError::UnexpectedEof)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn parse_vlq_int(data: &mut &[u8]) -> Result<u32, MessageDecodeError> {
     let mut c = next_byte(data)? as u32;
     let mut v = c & 0x7F;
     if (c & 0x60) == 0x60 {
@@ -50,14 +87,163 @@ fn parse_vlq_int(data: &mut &[u8]) -> Result<u32, MessageDecodeError> {
     Ok(v)
 }
 
+fn parse_vlq_int_reader<R: std::io::Read>(reader: &mut R) -> Result<u32, MessageDecodeError> {
+    let mut c = next_byte_reader(reader)? as u32;
+    let mut v = c & 0x7F;
+    if (c & 0x60) == 0x60 {
+        v |= (-0x20i32) as u32;
+    }
+    while c & 0x80 != 0 {
+        c = next_byte_reader(reader)? as u32;
+        v = (v << 7) | (c & 0x7F);
+    }
+
+    Ok(v)
+}
+
+pub(crate) fn encode_vlq_int64(output: &mut Vec<u8>, v: u64) {
+    let sv = v as i64;
+    if !(-(1i64 << 61)..(3i64 << 61)).contains(&sv) {
+        output.push(((sv >> 63) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 54)..(3i64 << 54)).contains(&sv) {
+        output.push(((sv >> 56) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 47)..(3i64 << 47)).contains(&sv) {
+        output.push(((sv >> 49) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 40)..(3i64 << 40)).contains(&sv) {
+        output.push(((sv >> 42) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 33)..(3i64 << 33)).contains(&sv) {
+        output.push(((sv >> 35) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 26)..(3i64 << 26)).contains(&sv) {
+        output.push(((sv >> 28) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 19)..(3i64 << 19)).contains(&sv) {
+        output.push(((sv >> 21) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 12)..(3i64 << 12)).contains(&sv) {
+        output.push(((sv >> 14) & 0x7F) as u8 | 0x80);
+    }
+    if !(-(1i64 << 5)..(3i64 << 5)).contains(&sv) {
+        output.push(((sv >> 7) & 0x7F) as u8 | 0x80);
+    }
+    output.push((sv & 0x7F) as u8);
+}
+
+pub(crate) fn encode_vlq_int_writer<W: std::io::Write>(
+    writer: &mut W,
+    v: u32,
+) -> std::io::Result<()> {
+    let sv = v as i32;
+    if !(-(1 << 26)..(3 << 26)).contains(&sv) {
+        writer.write_all(&[((sv >> 28) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1 << 19)..(3 << 19)).contains(&sv) {
+        writer.write_all(&[((sv >> 21) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1 << 12)..(3 << 12)).contains(&sv) {
+        writer.write_all(&[((sv >> 14) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1 << 5)..(3 << 5)).contains(&sv) {
+        writer.write_all(&[((sv >> 7) & 0x7F) as u8 | 0x80])?;
+    }
+    writer.write_all(&[(sv & 0x7F) as u8])
+}
+
+pub(crate) fn parse_vlq_int64(data: &mut &[u8]) -> Result<u64, MessageDecodeError> {
+    let mut c = next_byte(data)? as u64;
+    let mut v = c & 0x7F;
+    if (c & 0x60) == 0x60 {
+        v |= (-0x20i64) as u64;
+    }
+    while c & 0x80 != 0 {
+        c = next_byte(data)? as u64;
+        v = (v << 7) | (c & 0x7F);
+    }
+
+    Ok(v)
+}
+
+fn parse_vlq_int64_reader<R: std::io::Read>(reader: &mut R) -> Result<u64, MessageDecodeError> {
+    let mut c = next_byte_reader(reader)? as u64;
+    let mut v = c & 0x7F;
+    if (c & 0x60) == 0x60 {
+        v |= (-0x20i64) as u64;
+    }
+    while c & 0x80 != 0 {
+        c = next_byte_reader(reader)? as u64;
+        v = (v << 7) | (c & 0x7F);
+    }
+
+    Ok(v)
+}
+
+pub(crate) fn encode_vlq_int64_writer<W: std::io::Write>(
+    writer: &mut W,
+    v: u64,
+) -> std::io::Result<()> {
+    let sv = v as i64;
+    if !(-(1i64 << 61)..(3i64 << 61)).contains(&sv) {
+        writer.write_all(&[((sv >> 63) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 54)..(3i64 << 54)).contains(&sv) {
+        writer.write_all(&[((sv >> 56) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 47)..(3i64 << 47)).contains(&sv) {
+        writer.write_all(&[((sv >> 49) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 40)..(3i64 << 40)).contains(&sv) {
+        writer.write_all(&[((sv >> 42) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 33)..(3i64 << 33)).contains(&sv) {
+        writer.write_all(&[((sv >> 35) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 26)..(3i64 << 26)).contains(&sv) {
+        writer.write_all(&[((sv >> 28) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 19)..(3i64 << 19)).contains(&sv) {
+        writer.write_all(&[((sv >> 21) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 12)..(3i64 << 12)).contains(&sv) {
+        writer.write_all(&[((sv >> 14) & 0x7F) as u8 | 0x80])?;
+    }
+    if !(-(1i64 << 5)..(3i64 << 5)).contains(&sv) {
+        writer.write_all(&[((sv >> 7) & 0x7F) as u8 | 0x80])?;
+    }
+    writer.write_all(&[(sv & 0x7F) as u8])
+}
+
 pub trait Readable<'de>: Sized {
     fn read(data: &mut &'de [u8]) -> Result<Self, MessageDecodeError>;
 
     fn skip(data: &mut &[u8]) -> Result<(), MessageDecodeError>;
 }
 
+/// Like [`Readable`], but pulls its bytes on demand from a [`std::io::Read`]
+/// instead of a pre-buffered slice.
+///
+/// Only implemented for types that don't need to borrow from the input
+/// (`&str`/`&[u8]` stay slice-only, since there is no buffer to borrow from
+/// here); owned numeric and boolean fields can be decoded one byte at a time.
+pub trait ReadableFromReader: Sized {
+    fn read_from<R: std::io::Read>(reader: &mut R) -> Result<Self, MessageDecodeError>;
+}
+
 pub trait Writable: Sized {
     fn write(&self, output: &mut Vec<u8>);
+
+    /// Writes directly to a [`std::io::Write`] without materializing the
+    /// whole value in a buffer first. The default buffers via [`Self::write`]
+    /// for types that don't override it; every type in this module provides
+    /// a streaming override.
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let mut buf = Vec::new();
+        self.write(&mut buf);
+        writer.write_all(&buf)
+    }
 }
 
 pub trait Ownable: Sized {
@@ -81,10 +267,63 @@ macro_rules! int_readwrite {
             }
         }
 
+        impl ReadableFromReader for $type {
+            fn read_from<R: std::io::Read>(reader: &mut R) -> Result<Self, MessageDecodeError> {
+                parse_vlq_int_reader(reader).map(|v| v as $type)
+            }
+        }
+
         impl Writable for $type {
             fn write(&self, output: &mut Vec<u8>) {
                 encode_vlq_int(output, *self as u32)
             }
+
+            fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                encode_vlq_int_writer(writer, *self as u32)
+            }
+        }
+
+        impl Ownable for $type {
+            type Owned = Self;
+            fn to_owned(&self) -> Self::Owned {
+                *self
+            }
+        }
+
+        impl ToFieldType for $type {
+            fn as_field_type() -> FieldType {
+                $field_type
+            }
+        }
+    };
+}
+
+macro_rules! int_readwrite64 {
+    ( $type:tt, $field_type:expr ) => {
+        impl Readable<'_> for $type {
+            fn read(data: &mut &[u8]) -> Result<Self, MessageDecodeError> {
+                parse_vlq_int64(data).map(|v| v as $type)
+            }
+
+            fn skip(data: &mut &[u8]) -> Result<(), MessageDecodeError> {
+                parse_vlq_int64(data).map(|_| ())
+            }
+        }
+
+        impl ReadableFromReader for $type {
+            fn read_from<R: std::io::Read>(reader: &mut R) -> Result<Self, MessageDecodeError> {
+                parse_vlq_int64_reader(reader).map(|v| v as $type)
+            }
+        }
+
+        impl Writable for $type {
+            fn write(&self, output: &mut Vec<u8>) {
+                encode_vlq_int64(output, *self as u64)
+            }
+
+            fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+                encode_vlq_int64_writer(writer, *self as u64)
+            }
         }
 
         impl Ownable for $type {
@@ -107,6 +346,8 @@ int_readwrite!(i32, FieldType::I32);
 int_readwrite!(u16, FieldType::U16);
 int_readwrite!(i16, FieldType::I16);
 int_readwrite!(u8, FieldType::U8);
+int_readwrite64!(u64, FieldType::U64);
+int_readwrite64!(i64, FieldType::I64);
 
 impl Readable<'_> for bool {
     fn read(data: &mut &[u8]) -> Result<Self, MessageDecodeError> {
@@ -118,10 +359,20 @@ impl Readable<'_> for bool {
     }
 }
 
+impl ReadableFromReader for bool {
+    fn read_from<R: std::io::Read>(reader: &mut R) -> Result<Self, MessageDecodeError> {
+        parse_vlq_int_reader(reader).map(|v| v != 0)
+    }
+}
+
 impl Writable for bool {
     fn write(&self, output: &mut Vec<u8>) {
         encode_vlq_int(output, u32::from(*self))
     }
+
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        encode_vlq_int_writer(writer, u32::from(*self))
+    }
 }
 
 impl Ownable for bool {
@@ -165,6 +416,11 @@ impl Writable for &[u8] {
         encode_vlq_int(output, self.len() as u32);
         output.extend_from_slice(self);
     }
+
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        encode_vlq_int_writer(writer, self.len() as u32)?;
+        writer.write_all(self)
+    }
 }
 
 impl Ownable for &[u8] {
@@ -209,6 +465,12 @@ impl Writable for &str {
         encode_vlq_int(output, bytes.len() as u32);
         output.extend_from_slice(bytes);
     }
+
+    fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let bytes = self.as_bytes();
+        encode_vlq_int_writer(writer, bytes.len() as u32)?;
+        writer.write_all(bytes)
+    }
 }
 
 impl Ownable for &str {
@@ -231,11 +493,18 @@ pub enum FieldType {
     U16,
     I16,
     U8,
+    U64,
+    I64,
     String,
     ByteArray,
 }
 
 impl FieldType {
+    // Kept in lockstep with `read` for composite message layouts that need to
+    // discard a field by its runtime `FieldType` rather than a static type;
+    // the TLV reader in `crate::tlv` always wants the decoded `FieldValue`, so
+    // it doesn't call this itself.
+    #[allow(dead_code)]
     pub(crate) fn skip(&self, input: &mut &[u8]) -> Result<(), MessageDecodeError> {
         match self {
             Self::U32 => <u32 as Readable>::skip(input),
@@ -243,6 +512,8 @@ impl FieldType {
             Self::U16 => <u16 as Readable>::skip(input),
             Self::I16 => <i16 as Readable>::skip(input),
             Self::U8 => <u8 as Readable>::skip(input),
+            Self::U64 => <u64 as Readable>::skip(input),
+            Self::I64 => <i64 as Readable>::skip(input),
             Self::String => <&str as Readable>::skip(input),
             Self::ByteArray => <&[u8] as Readable>::skip(input),
         }
@@ -255,19 +526,135 @@ impl FieldType {
             Self::U16 => FieldValue::U16(<u16 as Readable>::read(input)?),
             Self::I16 => FieldValue::I16(<i16 as Readable>::read(input)?),
             Self::U8 => FieldValue::U8(<u8 as Readable>::read(input)?),
+            Self::U64 => FieldValue::U64(<u64 as Readable>::read(input)?),
+            Self::I64 => FieldValue::I64(<i64 as Readable>::read(input)?),
             Self::String => FieldValue::String(<&str as Readable>::read(input)?.into()),
             Self::ByteArray => FieldValue::ByteArray(<&[u8] as Readable>::read(input)?.into()),
         })
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum FieldValue {
     U32(u32),
     I32(i32),
     U16(u16),
     I16(i16),
     U8(u8),
+    U64(u64),
+    I64(i64),
     String(String),
     ByteArray(Vec<u8>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roundtrip32(v: i32) {
+        let mut buf = Vec::new();
+        encode_vlq_int(&mut buf, v as u32);
+        let mut slice = &buf[..];
+        assert_eq!(parse_vlq_int(&mut slice).unwrap() as i32, v);
+        assert!(slice.is_empty());
+    }
+
+    fn roundtrip64(v: i64) {
+        let mut buf = Vec::new();
+        encode_vlq_int64(&mut buf, v as u64);
+        let mut slice = &buf[..];
+        assert_eq!(parse_vlq_int64(&mut slice).unwrap() as i64, v);
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn vlq32_round_trips_at_each_boundary() {
+        for shift in [0, 5, 12, 19, 26] {
+            for delta in [-1i64, 0, 1] {
+                let v = (1i64 << shift) + delta;
+                roundtrip32(v as i32);
+                roundtrip32((-v) as i32);
+            }
+        }
+        roundtrip32(0);
+        roundtrip32(i32::MIN);
+        roundtrip32(i32::MAX);
+    }
+
+    #[test]
+    fn vlq32_wire_format_is_unchanged() {
+        let mut buf = Vec::new();
+        encode_vlq_int(&mut buf, 0u32);
+        assert_eq!(buf, [0x00]);
+
+        let mut buf = Vec::new();
+        encode_vlq_int(&mut buf, 300u32);
+        assert_eq!(buf, [0x82, 0x2C]);
+
+        let mut buf = Vec::new();
+        encode_vlq_int(&mut buf, (-1i32) as u32);
+        assert_eq!(buf, [0x7F]);
+    }
+
+    #[test]
+    fn vlq64_round_trips_at_each_boundary() {
+        for shift in [0, 5, 12, 19, 26, 33, 40, 47, 54, 61] {
+            for delta in [-1i64, 0, 1] {
+                let v = (1i64 << shift).wrapping_add(delta);
+                roundtrip64(v);
+                roundtrip64(-v);
+            }
+        }
+        roundtrip64(0);
+        roundtrip64(i64::MIN);
+        roundtrip64(i64::MAX);
+    }
+
+    #[test]
+    fn u64_i64_field_values_round_trip_through_field_type() {
+        let mut buf = Vec::new();
+        42u64.write(&mut buf);
+        (-7i64).write(&mut buf);
+
+        let mut slice = &buf[..];
+        assert_eq!(FieldType::U64.read(&mut slice).unwrap(), FieldValue::U64(42));
+        assert_eq!(FieldType::I64.read(&mut slice).unwrap(), FieldValue::I64(-7));
+        assert!(slice.is_empty());
+    }
+
+    #[test]
+    fn reader_and_slice_decode_agree() {
+        let mut buf = Vec::new();
+        encode_vlq_int64(&mut buf, u64::MAX);
+
+        let mut slice = &buf[..];
+        let from_slice = parse_vlq_int64(&mut slice).unwrap();
+
+        let mut reader = &buf[..];
+        let from_reader = parse_vlq_int64_reader(&mut reader).unwrap();
+
+        assert_eq!(from_slice, from_reader);
+    }
+
+    #[test]
+    fn reader_reports_eof_past_the_end() {
+        let mut reader: &[u8] = &[];
+        assert!(matches!(
+            next_byte_reader(&mut reader),
+            Err(MessageDecodeError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn write_to_and_read_from_round_trip_through_a_cursor() {
+        let mut buf = Vec::new();
+        42u32.write_to(&mut buf).unwrap();
+        (-7i64).write_to(&mut buf).unwrap();
+        true.write_to(&mut buf).unwrap();
+
+        let mut cursor = std::io::Cursor::new(buf);
+        assert_eq!(u32::read_from(&mut cursor).unwrap(), 42u32);
+        assert_eq!(i64::read_from(&mut cursor).unwrap(), -7i64);
+        assert!(bool::read_from(&mut cursor).unwrap());
+    }
+}
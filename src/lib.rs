@@ -0,0 +1,5 @@
+pub mod encoding;
+pub mod text;
+pub mod tlv;
+
+pub use windlass_derive::Message;
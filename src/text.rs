@@ -0,0 +1,283 @@
+//! A human-readable, round-trippable syntax for [`FieldValue`]s, inspired by
+//! the text transfer syntax in [Preserves](https://preserves.dev/). Integers
+//! render as decimal, strings as double-quoted escaped literals, and byte
+//! arrays as `#"<hex>"` literals. This is for inspecting and diffing captured
+//! MCU traffic, not for production encoding — the binary codec in
+//! [`crate::encoding`] remains the wire format.
+
+use crate::encoding::{FieldType, FieldValue};
+
+/// An error produced while parsing the text syntax back into [`FieldValue`]s.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum TextDecodeError {
+    #[error("expected a quoted string")]
+    ExpectedString,
+    #[error("unterminated string literal")]
+    UnterminatedString,
+    #[error("invalid escape sequence in string literal")]
+    InvalidEscape,
+    #[error("expected a #\"...\" byte array literal")]
+    ExpectedByteArray,
+    #[error("unterminated byte array literal")]
+    UnterminatedByteArray,
+    #[error("invalid hex digit in byte array literal")]
+    InvalidHex,
+    #[error("invalid integer literal: {0}")]
+    InvalidInteger(String),
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("trailing input after the last field")]
+    TrailingInput,
+}
+
+impl FieldValue {
+    /// Renders this value in the text syntax.
+    pub fn to_text(&self) -> String {
+        match self {
+            FieldValue::U32(v) => v.to_string(),
+            FieldValue::I32(v) => v.to_string(),
+            FieldValue::U16(v) => v.to_string(),
+            FieldValue::I16(v) => v.to_string(),
+            FieldValue::U8(v) => v.to_string(),
+            FieldValue::U64(v) => v.to_string(),
+            FieldValue::I64(v) => v.to_string(),
+            FieldValue::String(s) => escape_string(s),
+            FieldValue::ByteArray(b) => format!("#\"{}\"", encode_hex(b)),
+        }
+    }
+}
+
+/// Renders a whole message's decoded fields, space-separated.
+pub fn message_to_text(values: &[FieldValue]) -> String {
+    values
+        .iter()
+        .map(FieldValue::to_text)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parses `input` back into a [`FieldValue`] per `field_types`, in order.
+pub fn from_text(
+    input: &str,
+    field_types: &[FieldType],
+) -> Result<Vec<FieldValue>, TextDecodeError> {
+    let mut rest = input.trim_start();
+    let mut values = Vec::with_capacity(field_types.len());
+    for field_type in field_types {
+        let (value, tail) = parse_one(rest, *field_type)?;
+        values.push(value);
+        rest = tail.trim_start();
+    }
+    if !rest.is_empty() {
+        return Err(TextDecodeError::TrailingInput);
+    }
+    Ok(values)
+}
+
+fn parse_one(input: &str, field_type: FieldType) -> Result<(FieldValue, &str), TextDecodeError> {
+    match field_type {
+        FieldType::String => parse_string(input),
+        FieldType::ByteArray => parse_bytearray(input),
+        FieldType::U32
+        | FieldType::I32
+        | FieldType::U16
+        | FieldType::I16
+        | FieldType::U8
+        | FieldType::U64
+        | FieldType::I64 => parse_int(input, field_type),
+    }
+}
+
+fn parse_int(input: &str, field_type: FieldType) -> Result<(FieldValue, &str), TextDecodeError> {
+    let end = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '-'))
+        .unwrap_or(input.len());
+    if end == 0 {
+        return Err(match input.split_whitespace().next() {
+            None => TextDecodeError::UnexpectedEnd,
+            Some(token) => TextDecodeError::InvalidInteger(token.to_string()),
+        });
+    }
+    let token = &input[..end];
+    let rest = &input[end..];
+    let invalid = || TextDecodeError::InvalidInteger(token.to_string());
+    let value = match field_type {
+        FieldType::U32 => FieldValue::U32(token.parse().map_err(|_| invalid())?),
+        FieldType::I32 => FieldValue::I32(token.parse().map_err(|_| invalid())?),
+        FieldType::U16 => FieldValue::U16(token.parse().map_err(|_| invalid())?),
+        FieldType::I16 => FieldValue::I16(token.parse().map_err(|_| invalid())?),
+        FieldType::U8 => FieldValue::U8(token.parse().map_err(|_| invalid())?),
+        FieldType::U64 => FieldValue::U64(token.parse().map_err(|_| invalid())?),
+        FieldType::I64 => FieldValue::I64(token.parse().map_err(|_| invalid())?),
+        FieldType::String | FieldType::ByteArray => unreachable!(),
+    };
+    Ok((value, rest))
+}
+
+fn parse_string(input: &str) -> Result<(FieldValue, &str), TextDecodeError> {
+    let mut chars = input.char_indices();
+    match chars.next() {
+        Some((_, '"')) => {}
+        _ => return Err(TextDecodeError::ExpectedString),
+    }
+    let mut s = String::new();
+    loop {
+        match chars.next() {
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '"')) => s.push('"'),
+                Some((_, '\\')) => s.push('\\'),
+                Some((_, 'n')) => s.push('\n'),
+                Some((_, 'r')) => s.push('\r'),
+                Some((_, 't')) => s.push('\t'),
+                _ => return Err(TextDecodeError::InvalidEscape),
+            },
+            Some((i, '"')) => return Ok((FieldValue::String(s), &input[i + 1..])),
+            Some((_, c)) => s.push(c),
+            None => return Err(TextDecodeError::UnterminatedString),
+        }
+    }
+}
+
+fn parse_bytearray(input: &str) -> Result<(FieldValue, &str), TextDecodeError> {
+    let rest = input
+        .strip_prefix("#\"")
+        .ok_or(TextDecodeError::ExpectedByteArray)?;
+    let end = rest
+        .find('"')
+        .ok_or(TextDecodeError::UnterminatedByteArray)?;
+    let bytes = decode_hex(&rest[..end])?;
+    Ok((FieldValue::ByteArray(bytes), &rest[end + 1..]))
+}
+
+fn escape_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        write!(out, "{b:02x}").expect("writing to a String can't fail");
+    }
+    out
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, TextDecodeError> {
+    if !s.len().is_multiple_of(2) {
+        return Err(TextDecodeError::InvalidHex);
+    }
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len() / 2);
+    for chunk in bytes.chunks(2) {
+        out.push((hex_digit(chunk[0])? << 4) | hex_digit(chunk[1])?);
+    }
+    Ok(out)
+}
+
+fn hex_digit(b: u8) -> Result<u8, TextDecodeError> {
+    match b {
+        b'0'..=b'9' => Ok(b - b'0'),
+        b'a'..=b'f' => Ok(b - b'a' + 10),
+        b'A'..=b'F' => Ok(b - b'A' + 10),
+        _ => Err(TextDecodeError::InvalidHex),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers_round_trip_through_text() {
+        let values = [
+            FieldValue::U32(42),
+            FieldValue::I32(-7),
+            FieldValue::U16(300),
+            FieldValue::I16(-300),
+            FieldValue::U8(255),
+            FieldValue::U64(u64::MAX),
+            FieldValue::I64(i64::MIN),
+        ];
+        let field_types = [
+            FieldType::U32,
+            FieldType::I32,
+            FieldType::U16,
+            FieldType::I16,
+            FieldType::U8,
+            FieldType::U64,
+            FieldType::I64,
+        ];
+
+        let text = message_to_text(&values);
+        let decoded = from_text(&text, &field_types).unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn string_with_escapes_round_trips() {
+        let value = FieldValue::String("quote \" backslash \\ \n tab\t here".to_string());
+
+        let text = value.to_text();
+        assert_eq!(text, "\"quote \\\" backslash \\\\ \\n tab\\t here\"");
+
+        let (decoded, rest) = parse_string(&text).unwrap();
+        assert_eq!(decoded, value);
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn byte_array_round_trips_through_hex_literal() {
+        let value = FieldValue::ByteArray(vec![0x00, 0xde, 0xad, 0xbe, 0xef, 0xff]);
+
+        let text = value.to_text();
+        assert_eq!(text, "#\"00deadbeefff\"");
+
+        let decoded = from_text(&text, &[FieldType::ByteArray]).unwrap();
+        assert_eq!(decoded, vec![value]);
+    }
+
+    #[test]
+    fn whole_message_round_trips_space_separated() {
+        let values = vec![
+            FieldValue::U32(7),
+            FieldValue::String("hi there".to_string()),
+            FieldValue::ByteArray(vec![1, 2, 3]),
+        ];
+        let field_types = [FieldType::U32, FieldType::String, FieldType::ByteArray];
+
+        let text = message_to_text(&values);
+        let decoded = from_text(&text, &field_types).unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn trailing_input_after_the_last_field_is_rejected() {
+        let err = from_text("7 extra", &[FieldType::U32]).unwrap_err();
+        assert_eq!(err, TextDecodeError::TrailingInput);
+    }
+
+    #[test]
+    fn unterminated_string_is_rejected() {
+        let err = from_text("\"oops", &[FieldType::String]).unwrap_err();
+        assert_eq!(err, TextDecodeError::UnterminatedString);
+    }
+
+    #[test]
+    fn invalid_hex_digit_is_rejected() {
+        let err = from_text("#\"zz\"", &[FieldType::ByteArray]).unwrap_err();
+        assert_eq!(err, TextDecodeError::InvalidHex);
+    }
+}
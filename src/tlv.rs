@@ -0,0 +1,183 @@
+//! TLV (type-length-value) extension records that can be appended after a
+//! message's fixed fields without breaking peers that don't know about them.
+//!
+//! Each record is `(type: vlq u64, length: vlq u32, value: length bytes)` and
+//! records must appear in strictly ascending `type` order. On read, an
+//! unrecognized **even** type is a hard error (it was meant to be
+//! understood), while an unrecognized **odd** type is returned as a raw
+//! [`FieldValue::ByteArray`] so the caller can still inspect or re-forward it.
+
+use crate::encoding::{
+    encode_vlq_int, encode_vlq_int64, parse_vlq_int, parse_vlq_int64, FieldType, FieldValue,
+    MessageDecodeError, Writable,
+};
+
+/// A single decoded TLV record.
+#[derive(Debug)]
+pub struct TlvRecord {
+    pub record_type: u64,
+    pub value: FieldValue,
+}
+
+/// Reads a TLV stream from the tail of a message.
+pub struct TlvStreamReader;
+
+impl TlvStreamReader {
+    /// Decodes every record in `data`, consuming it entirely.
+    ///
+    /// `known_types` maps a record's `type` to the [`FieldType`] its value
+    /// should be decoded as; any type not present there falls back to the
+    /// even/odd convention described on the module.
+    pub fn read(
+        data: &mut &[u8],
+        known_types: &[(u64, FieldType)],
+    ) -> Result<Vec<TlvRecord>, MessageDecodeError> {
+        let mut records = Vec::new();
+        let mut last_type: Option<u64> = None;
+
+        while !data.is_empty() {
+            let record_type = parse_vlq_int64(data)?;
+            if let Some(prev) = last_type {
+                if record_type <= prev {
+                    return Err(MessageDecodeError::TlvRecordsOutOfOrder {
+                        prev,
+                        next: record_type,
+                    });
+                }
+            }
+            last_type = Some(record_type);
+
+            let len = parse_vlq_int(data)? as usize;
+            if data.len() < len {
+                return Err(MessageDecodeError::UnexpectedEof);
+            }
+            let mut value = &data[..len];
+            *data = &data[len..];
+
+            let known = known_types
+                .iter()
+                .find(|(t, _)| *t == record_type)
+                .map(|(_, field_type)| field_type);
+            let value = match known {
+                Some(field_type) => field_type.read(&mut value)?,
+                None if record_type % 2 == 0 => {
+                    return Err(MessageDecodeError::UnknownRequiredTlvField(record_type))
+                }
+                None => FieldValue::ByteArray(value.to_vec()),
+            };
+
+            records.push(TlvRecord { record_type, value });
+        }
+
+        Ok(records)
+    }
+}
+
+/// Writes a TLV stream, trailing a message's fixed fields.
+pub struct TlvStreamWriter<'a> {
+    output: &'a mut Vec<u8>,
+    last_type: Option<u64>,
+}
+
+impl<'a> TlvStreamWriter<'a> {
+    pub fn new(output: &'a mut Vec<u8>) -> Self {
+        Self {
+            output,
+            last_type: None,
+        }
+    }
+
+    /// Appends a record. Callers must write records in strictly ascending
+    /// `record_type` order.
+    pub fn write_field(&mut self, record_type: u64, value: &impl Writable) {
+        debug_assert!(
+            self.last_type.is_none_or(|last| record_type > last),
+            "tlv record types must be written in strictly ascending order"
+        );
+        self.last_type = Some(record_type);
+
+        encode_vlq_int64(self.output, record_type);
+
+        let mut buf = Vec::new();
+        value.write(&mut buf);
+        encode_vlq_int(self.output, buf.len() as u32);
+        self.output.extend_from_slice(&buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(record_type: u64, value: &impl Writable) -> Vec<u8> {
+        let mut buf = Vec::new();
+        TlvStreamWriter::new(&mut buf).write_field(record_type, value);
+        buf
+    }
+
+    #[test]
+    fn known_records_round_trip_in_order() {
+        let mut buf = Vec::new();
+        let mut writer = TlvStreamWriter::new(&mut buf);
+        writer.write_field(2u64, &7u32);
+        writer.write_field(3u64, &"hello");
+
+        let mut data = &buf[..];
+        let records =
+            TlvStreamReader::read(&mut data, &[(2, FieldType::U32), (3, FieldType::String)])
+                .unwrap();
+
+        assert_eq!(records[0].record_type, 2);
+        assert_eq!(records[0].value, FieldValue::U32(7));
+        assert_eq!(records[1].record_type, 3);
+        assert_eq!(records[1].value, FieldValue::String("hello".to_string()));
+    }
+
+    #[test]
+    fn unknown_odd_record_is_skipped_as_raw_bytes() {
+        let buf = record(5u64, &"surprise");
+
+        let mut data = &buf[..];
+        let records = TlvStreamReader::read(&mut data, &[]).unwrap();
+
+        assert_eq!(records[0].record_type, 5);
+        // Unknown types come back as the raw TLV value bytes, which for a
+        // `&str` field is itself length-prefixed by `Writable::write`.
+        assert_eq!(
+            records[0].value,
+            FieldValue::ByteArray(b"\x08surprise".to_vec())
+        );
+    }
+
+    #[test]
+    fn unknown_even_record_is_a_hard_error() {
+        let buf = record(4u64, &9u32);
+
+        let mut data = &buf[..];
+        let err = TlvStreamReader::read(&mut data, &[]).unwrap_err();
+
+        assert!(matches!(
+            err,
+            MessageDecodeError::UnknownRequiredTlvField(4)
+        ));
+    }
+
+    #[test]
+    fn out_of_order_records_are_rejected_not_misreported_as_unknown() {
+        // Concatenate two individually well-formed records with type 5
+        // before type 3, so the only possible fault is ordering, not an
+        // unrecognized type (the reviewer's own repro: 5 then 3, both known).
+        let mut buf = record(5u64, &"first");
+        buf.extend(record(3u64, &"second"));
+
+        let mut data = &buf[..];
+        let err =
+            TlvStreamReader::read(&mut data, &[(3, FieldType::String), (5, FieldType::String)])
+                .unwrap_err();
+
+        assert_eq!(
+            err,
+            MessageDecodeError::TlvRecordsOutOfOrder { prev: 5, next: 3 }
+        );
+    }
+}
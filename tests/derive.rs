@@ -0,0 +1,94 @@
+use windlass::encoding::{FieldType, Ownable, Readable, Writable};
+use windlass::Message;
+
+#[derive(Message, Debug, PartialEq)]
+struct Ping<'de> {
+    nonce: u32,
+    label: &'de str,
+    payload: &'de [u8],
+}
+
+#[test]
+fn derived_struct_round_trips_through_write_and_read() {
+    let ping = Ping {
+        nonce: 42,
+        label: "hello",
+        payload: &[1, 2, 3],
+    };
+
+    let mut buf = Vec::new();
+    ping.write(&mut buf);
+
+    let mut data = &buf[..];
+    let decoded = Ping::read(&mut data).unwrap();
+
+    assert_eq!(decoded, ping);
+    assert!(data.is_empty());
+}
+
+#[test]
+fn derived_field_types_match_declaration_order() {
+    assert_eq!(
+        Ping::field_types(),
+        vec![FieldType::U32, FieldType::String, FieldType::ByteArray]
+    );
+}
+
+#[test]
+fn derived_ownable_twin_detaches_from_the_source_buffer() {
+    let buf = vec![9, 8, 7];
+    let ping = Ping {
+        nonce: 1,
+        label: "borrowed",
+        payload: &buf,
+    };
+
+    let owned: PingOwned = ping.to_owned();
+    drop(buf);
+
+    assert_eq!(owned.nonce, 1);
+    assert_eq!(owned.label, "borrowed".to_string());
+    assert_eq!(owned.payload, vec![9, 8, 7]);
+}
+
+// Regression test: a field literally named `data` used to shadow the
+// generated reader's cursor parameter, so every field after it decoded the
+// previous field's value instead of reading from the input slice.
+#[derive(Message, Debug, PartialEq)]
+struct Frame<'de> {
+    data: u32,
+    output: &'de str,
+}
+
+#[test]
+fn derived_write_to_forwards_to_each_field() {
+    let frame = Frame {
+        data: 7,
+        output: "trailer",
+    };
+
+    let mut via_write = Vec::new();
+    frame.write(&mut via_write);
+
+    let mut via_write_to = Vec::new();
+    frame.write_to(&mut via_write_to).unwrap();
+
+    assert_eq!(via_write, via_write_to);
+}
+
+#[test]
+fn field_named_data_does_not_shadow_the_read_cursor() {
+    let frame = Frame {
+        data: 7,
+        output: "trailer",
+    };
+
+    let mut buf = Vec::new();
+    frame.write(&mut buf);
+
+    let mut cursor = &buf[..];
+    let decoded = Frame::read(&mut cursor).unwrap();
+
+    assert_eq!(decoded, frame);
+    assert!(cursor.is_empty());
+}
@@ -0,0 +1,142 @@
+//! `#[derive(Message)]` for `windlass` message structs.
+//!
+//! For a struct whose fields all implement `Readable`/`Writable`/`ToFieldType`
+//! (and `Ownable`), this generates:
+//!
+//! - `Readable::read`/`skip`, reading fields top-to-bottom
+//! - `Writable::write`, writing fields in the same order
+//! - an inherent `field_types()` descriptor built from each field's `ToFieldType`
+//! - `Ownable`, producing an owned twin struct (`&'de str` -> `String`,
+//!   `&'de [u8]` -> `Vec<u8>`)
+//!
+//! This keeps field order, read order, write order, and the `field_types()`
+//! descriptor from ever drifting apart, since they're all generated from the
+//! same struct definition.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::visit_mut::VisitMut;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Lifetime, Type};
+
+/// Replaces every occurrence of a specific lifetime with `'static` in a field
+/// type, so `<Ty as Ownable>::Owned` can be named outside the struct's own
+/// generics (the `Owned` projection never actually depends on the lifetime).
+struct Staticize<'a> {
+    target: &'a Lifetime,
+}
+
+impl VisitMut for Staticize<'_> {
+    fn visit_lifetime_mut(&mut self, lifetime: &mut Lifetime) {
+        if lifetime.ident == self.target.ident {
+            *lifetime = Lifetime::new("'static", Span::call_site());
+        }
+    }
+}
+
+fn staticized(ty: &Type, lifetime: &Lifetime) -> Type {
+    let mut ty = ty.clone();
+    Staticize { target: lifetime }.visit_type_mut(&mut ty);
+    ty
+}
+
+#[proc_macro_derive(Message)]
+pub fn derive_message(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => panic!("#[derive(Message)] only supports structs with named fields"),
+        },
+        _ => panic!("#[derive(Message)] only supports structs"),
+    };
+
+    let field_names: Vec<_> = fields.iter().map(|f| f.ident.clone().unwrap()).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| f.ty.clone()).collect();
+
+    let has_lifetime = input.generics.lifetimes().next().is_some();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(existing) => existing.lifetime.clone(),
+        None => Lifetime::new("'de", Span::call_site()),
+    };
+    let struct_ty = if has_lifetime {
+        quote! { #name<#lifetime> }
+    } else {
+        quote! { #name }
+    };
+
+    let read_fields = field_names.iter().zip(&field_types).map(|(name, ty)| {
+        quote! { let #name = <#ty as windlass::encoding::Readable>::read(__windlass_input)?; }
+    });
+    let skip_fields = field_types.iter().map(|ty| {
+        quote! { <#ty as windlass::encoding::Readable>::skip(data)?; }
+    });
+    let write_fields = field_names.iter().map(|field_name| {
+        quote! { windlass::encoding::Writable::write(&self.#field_name, output); }
+    });
+    let write_to_fields = field_names.iter().map(|field_name| {
+        quote! { windlass::encoding::Writable::write_to(&self.#field_name, __windlass_writer)?; }
+    });
+    let field_type_exprs = field_types.iter().map(|ty| {
+        quote! { <#ty as windlass::encoding::ToFieldType>::as_field_type() }
+    });
+
+    let owned_name = format_ident!("{name}Owned");
+    let owned_field_decls = field_names.iter().zip(&field_types).map(|(field_name, ty)| {
+        let owned_ty = staticized(ty, &lifetime);
+        quote! { pub #field_name: <#owned_ty as windlass::encoding::Ownable>::Owned }
+    });
+    let owned_field_inits = field_names.iter().map(|field_name| {
+        quote! { #field_name: windlass::encoding::Ownable::to_owned(&self.#field_name) }
+    });
+
+    let expanded = quote! {
+        impl<#lifetime> windlass::encoding::Readable<#lifetime> for #struct_ty {
+            fn read(__windlass_input: &mut &#lifetime [u8]) -> Result<Self, windlass::encoding::MessageDecodeError> {
+                #(#read_fields)*
+                Ok(Self { #(#field_names),* })
+            }
+
+            fn skip(data: &mut &[u8]) -> Result<(), windlass::encoding::MessageDecodeError> {
+                #(#skip_fields)*
+                Ok(())
+            }
+        }
+
+        impl<#lifetime> windlass::encoding::Writable for #struct_ty {
+            fn write(&self, output: &mut Vec<u8>) {
+                #(#write_fields)*
+            }
+
+            fn write_to<__WindlassWriter: std::io::Write>(
+                &self,
+                __windlass_writer: &mut __WindlassWriter,
+            ) -> std::io::Result<()> {
+                #(#write_to_fields)*
+                Ok(())
+            }
+        }
+
+        impl<#lifetime> #struct_ty {
+            pub fn field_types() -> Vec<windlass::encoding::FieldType> {
+                vec![#(#field_type_exprs),*]
+            }
+        }
+
+        pub struct #owned_name {
+            #(#owned_field_decls),*
+        }
+
+        impl<#lifetime> windlass::encoding::Ownable for #struct_ty {
+            type Owned = #owned_name;
+
+            fn to_owned(&self) -> Self::Owned {
+                #owned_name { #(#owned_field_inits),* }
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}